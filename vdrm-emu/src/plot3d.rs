@@ -1,14 +1,26 @@
 use crate::DrawResult;
 use plotters::coord::ranged3d::ProjectionMatrix;
+use plotters::coord::CoordTranslate;
 use plotters::prelude::*;
 use plotters_canvas::CanvasBackend;
 use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Mutex;
 use web_sys::HtmlCanvasElement;
 
 lazy_static::lazy_static! {
-    static ref CTX: Ctx = {
-        Ctx::new()
+    // Behind a `Mutex` (rather than a plain `Ctx`) so `load_model` can swap in a newly imported
+    // model at runtime instead of only ever showing the pyramid baked in at startup.
+    static ref CTX: Mutex<Ctx> = {
+        Mutex::new(Ctx::new())
     };
+    // The forward coordinate transform (scene point -> canvas pixel) of the most recent `draw()`
+    // call's projection, so `pick` can find which known point a clicked pixel landed on. 3D
+    // perspective projections aren't invertible (a pixel is a ray, not a point), so `pick` works
+    // by projecting every candidate point forward and finding the nearest one in pixel space,
+    // rather than trying to reverse-map the click.
+    static ref LAST_PROJECTION: Mutex<Option<Box<dyn Fn((f32, f32, f32)) -> (i32, i32) + Send>>> =
+        Mutex::new(None);
 }
 
 pub fn gen_pyramid_surface() -> vdrm_alg::PixelSurface {
@@ -33,6 +45,212 @@ pub fn gen_pyramid_surface() -> vdrm_alg::PixelSurface {
     }
     pixel_surface
 }
+
+/// Something that can be parsed into a `vdrm_alg::PixelSurface`, so the emulator isn't limited to
+/// `gen_pyramid_surface`'s hardcoded test object.
+pub trait SurfaceModelLoader {
+    fn load(&self, data: &[u8]) -> Result<vdrm_alg::PixelSurface, ModelLoadError>;
+}
+
+#[derive(Debug)]
+pub enum ModelLoadError {
+    Truncated,
+    BadMagic,
+    Parse(String),
+}
+
+impl std::fmt::Display for ModelLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelLoadError::Truncated => write!(f, "truncated model data"),
+            ModelLoadError::BadMagic => write!(f, "unrecognized file signature"),
+            ModelLoadError::Parse(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ModelLoadError {}
+
+/// The built-in test pyramid, wrapped so it can be selected through the same `SurfaceModelLoader`
+/// trait as imported models.
+pub struct PyramidModel;
+
+impl SurfaceModelLoader for PyramidModel {
+    fn load(&self, _data: &[u8]) -> Result<vdrm_alg::PixelSurface, ModelLoadError> {
+        Ok(gen_pyramid_surface())
+    }
+}
+
+/// Quantizes an 8-bit RGB palette color down to this display's 3-bit color field, one bit per
+/// channel.
+fn quantize_color(r: u8, g: u8, b: u8) -> u32 {
+    (((r >= 128) as u32) << 2) | (((g >= 128) as u32) << 1) | (b >= 128) as u32
+}
+
+/// Quantizes a value from `[min, max]` onto the `0..64` grid `PixelSurface` expects.
+fn quantize_to_grid(value: f32, min: f32, max: f32) -> u32 {
+    let span = (max - min).max(f32::EPSILON);
+    (((value - min) / span) * 63.0).round().clamp(0.0, 63.0) as u32
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ModelLoadError> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(ModelLoadError::Truncated)
+}
+
+/// Loads a MagicaVoxel `.vox` file: each filled voxel becomes a `(x, y, (z, color))` entry after
+/// quantizing its palette color to the 3-bit field.
+pub struct VoxModel;
+
+impl SurfaceModelLoader for VoxModel {
+    fn load(&self, data: &[u8]) -> Result<vdrm_alg::PixelSurface, ModelLoadError> {
+        if data.len() < 8 || &data[0..4] != b"VOX " {
+            return Err(ModelLoadError::BadMagic);
+        }
+
+        let mut pos = 8; // magic + version
+        let mut z_size = None;
+        let mut voxels: Vec<(u32, u32, u32, u8)> = vec![];
+        let mut palette = [(255_u8, 255_u8, 255_u8); 256];
+
+        while pos.checked_add(12).is_some_and(|header_end| header_end <= data.len()) {
+            let id = &data[pos..pos + 4];
+            let content_size = read_u32(data, pos + 4)? as usize;
+            let children_size = read_u32(data, pos + 8)? as usize;
+            let content_start = pos + 12;
+            // `content_size`/`children_size` come straight from the file, so an offset built
+            // from them can overflow `usize` on wasm32's 32-bit pointers before `.get()` ever
+            // gets a chance to reject it out of range; check the arithmetic itself first.
+            let content_end = content_start
+                .checked_add(content_size)
+                .ok_or(ModelLoadError::Truncated)?;
+            let content = data
+                .get(content_start..content_end)
+                .ok_or(ModelLoadError::Truncated)?;
+
+            if id == b"SIZE" {
+                z_size = Some(read_u32(content, 8)?);
+            } else if id == b"XYZI" {
+                let n = read_u32(content, 0)? as usize;
+                for i in 0..n {
+                    let base = 4 + i * 4;
+                    let voxel = content
+                        .get(base..base + 4)
+                        .ok_or(ModelLoadError::Truncated)?;
+                    voxels.push((voxel[0] as u32, voxel[1] as u32, voxel[2] as u32, voxel[3]));
+                }
+            } else if id == b"RGBA" {
+                for (i, slot) in palette.iter_mut().enumerate() {
+                    let base = i * 4;
+                    if let Some(rgba) = content.get(base..base + 4) {
+                        *slot = (rgba[0], rgba[1], rgba[2]);
+                    }
+                }
+            }
+            // `MAIN` is a pure container: its own content is empty and every real chunk
+            // (`SIZE`/`XYZI`/`RGBA`/...) lives in its children region, so only skip past its
+            // header+content and keep iterating into the children. Leaf chunks have no children
+            // of their own (`children_size` is 0), so skipping past both is equivalent for them.
+            pos = if id == b"MAIN" {
+                content_end
+            } else {
+                content_end
+                    .checked_add(children_size)
+                    .ok_or(ModelLoadError::Truncated)?
+            };
+        }
+
+        let z_size = z_size.ok_or_else(|| ModelLoadError::Parse("missing SIZE chunk".into()))?;
+
+        let mut pixel_surface = vdrm_alg::PixelSurface::new();
+        for (x, y, z, color_index) in voxels {
+            if x >= 64 || y >= 64 {
+                continue;
+            }
+            let scaled_z = quantize_to_grid(z as f32, 0.0, (z_size.max(1) - 1) as f32);
+            let (r, g, b) = palette[color_index.wrapping_sub(1) as usize];
+            pixel_surface.push((x, y, (scaled_z, quantize_color(r, g, b))));
+        }
+        Ok(pixel_surface)
+    }
+}
+
+/// Loads an ASCII PLY point cloud with `x y z r g b` vertex properties (the common XYZRGB
+/// layout), quantizing positions onto the `0..64` grid and `z` into the height range.
+pub struct PlyModel;
+
+impl SurfaceModelLoader for PlyModel {
+    fn load(&self, data: &[u8]) -> Result<vdrm_alg::PixelSurface, ModelLoadError> {
+        let text = std::str::from_utf8(data).map_err(|e| ModelLoadError::Parse(e.to_string()))?;
+        let mut lines = text.lines();
+        let mut vertex_count = None;
+        for line in &mut lines {
+            let line = line.trim();
+            if line == "end_header" {
+                break;
+            }
+            if let Some(rest) = line.strip_prefix("element vertex ") {
+                vertex_count = Some(
+                    rest.trim()
+                        .parse::<usize>()
+                        .map_err(|e| ModelLoadError::Parse(e.to_string()))?,
+                );
+            }
+        }
+        let vertex_count =
+            vertex_count.ok_or_else(|| ModelLoadError::Parse("missing vertex element".into()))?;
+
+        // Not `Vec::with_capacity(vertex_count)`: that count comes straight from the file header,
+        // so a truncated/malicious file claiming billions of vertices would reserve that much
+        // memory up front before a single vertex line is even parsed. Growing with `push`
+        // instead keeps this self-limiting like `VoxModel`'s `XYZI` loop, which only ever
+        // allocates as much as `lines` actually yields.
+        let mut points = Vec::new();
+        for line in lines.take(vertex_count) {
+            let mut fields = line.split_whitespace();
+            let mut next_f32 = || -> Result<f32, ModelLoadError> {
+                fields
+                    .next()
+                    .ok_or_else(|| ModelLoadError::Parse("short vertex line".into()))?
+                    .parse()
+                    .map_err(|e: std::num::ParseFloatError| ModelLoadError::Parse(e.to_string()))
+            };
+            let (x, y, z) = (next_f32()?, next_f32()?, next_f32()?);
+            let mut next_u8 = || -> Result<u8, ModelLoadError> {
+                fields
+                    .next()
+                    .ok_or_else(|| ModelLoadError::Parse("short vertex line".into()))?
+                    .parse()
+                    .map_err(|e: std::num::ParseIntError| ModelLoadError::Parse(e.to_string()))
+            };
+            let (r, g, b) = (next_u8()?, next_u8()?, next_u8()?);
+            points.push((x, y, z, r, g, b));
+        }
+
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+        let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+        let (mut z_min, mut z_max) = (f32::MAX, f32::MIN);
+        for &(x, y, z, ..) in &points {
+            x_min = x_min.min(x);
+            x_max = x_max.max(x);
+            y_min = y_min.min(y);
+            y_max = y_max.max(y);
+            z_min = z_min.min(z);
+            z_max = z_max.max(z);
+        }
+
+        let mut pixel_surface = vdrm_alg::PixelSurface::new();
+        for (x, y, z, r, g, b) in points {
+            let gx = quantize_to_grid(x, x_min, x_max);
+            let gy = quantize_to_grid(y, y_min, y_max);
+            let gz = quantize_to_grid(z, z_min, z_max);
+            pixel_surface.push((gx, gy, (gz, quantize_color(r, g, b))));
+        }
+        Ok(pixel_surface)
+    }
+}
+
 struct Mirror {
     points: [(f32, f32, f32); 4],
 }
@@ -57,19 +275,182 @@ impl Mirror {
     }
 }
 
+/// A 2D projective homography (8 degrees of freedom, `h33` normalized to 1) used to calibrate
+/// the real, measured position of a panel against the coordinates `vdrm_alg::screens()` assumes
+/// for a perfectly placed one.
+#[derive(Clone, Copy, Debug)]
+pub struct Homography {
+    m: [f32; 9],
+}
+
+impl Homography {
+    fn identity() -> Self {
+        Self {
+            m: [1., 0., 0., 0., 1., 0., 0., 0., 1.],
+        }
+    }
+
+    /// Solves the homography mapping each `src[i]` to `dst[i]` for four measured corner
+    /// correspondences.
+    pub fn from_correspondences(src: [(f32, f32); 4], dst: [(f32, f32); 4]) -> Self {
+        let mut rows = [[0.0_f64; 9]; 8];
+        for i in 0..4 {
+            let (x, y) = (src[i].0 as f64, src[i].1 as f64);
+            let (u, v) = (dst[i].0 as f64, dst[i].1 as f64);
+            rows[2 * i] = [x, y, 1., 0., 0., 0., -u * x, -u * y, u];
+            rows[2 * i + 1] = [0., 0., 0., x, y, 1., -v * x, -v * y, v];
+        }
+        let h = solve_homogeneous_8x8(rows);
+        let mut m = [0.0_f32; 9];
+        for (i, coeff) in h.into_iter().enumerate() {
+            m[i] = coeff as f32;
+        }
+        m[8] = 1.0;
+        Self { m }
+    }
+
+    fn apply(&self, p: (f32, f32)) -> (f32, f32) {
+        let (x, y) = p;
+        let w = self.m[6] * x + self.m[7] * y + self.m[8];
+        (
+            (self.m[0] * x + self.m[1] * y + self.m[2]) / w,
+            (self.m[3] * x + self.m[4] * y + self.m[5]) / w,
+        )
+    }
+
+    /// The inverse homography, via the analytic 3x3 adjugate.
+    pub fn inverse(&self) -> Self {
+        let m = self.m;
+        let det = m[0] * (m[4] * m[8] - m[5] * m[7]) - m[1] * (m[3] * m[8] - m[5] * m[6])
+            + m[2] * (m[3] * m[7] - m[4] * m[6]);
+        let adj = [
+            m[4] * m[8] - m[5] * m[7],
+            m[2] * m[7] - m[1] * m[8],
+            m[1] * m[5] - m[2] * m[4],
+            m[5] * m[6] - m[3] * m[8],
+            m[0] * m[8] - m[2] * m[6],
+            m[2] * m[3] - m[0] * m[5],
+            m[3] * m[7] - m[4] * m[6],
+            m[1] * m[6] - m[0] * m[7],
+            m[0] * m[4] - m[1] * m[3],
+        ];
+        let scale = adj[8] / det;
+        let mut m = adj.map(|v| v / det);
+        for v in &mut m {
+            *v /= scale;
+        }
+        Self { m }
+    }
+}
+
+/// Gaussian elimination with partial pivoting for the 8-unknown linear system a homography
+/// correspondence solve reduces to.
+fn solve_homogeneous_8x8(mut rows: [[f64; 9]; 8]) -> [f64; 8] {
+    for col in 0..8 {
+        let pivot = (col..8)
+            .max_by(|&i, &j| rows[i][col].abs().partial_cmp(&rows[j][col].abs()).unwrap())
+            .unwrap();
+        rows.swap(col, pivot);
+        let pivot_val = rows[col][col];
+        for k in col..9 {
+            rows[col][k] /= pivot_val;
+        }
+        for row in 0..8 {
+            if row == col {
+                continue;
+            }
+            let factor = rows[row][col];
+            for k in col..9 {
+                rows[row][k] -= factor * rows[col][k];
+            }
+        }
+    }
+    let mut x = [0.0; 8];
+    for (i, slot) in x.iter_mut().enumerate() {
+        *slot = rows[i][8];
+    }
+    x
+}
+
+/// Builds a per-screen calibration from four measured corner correspondences each (the panel
+/// coordinates `vdrm_alg::screens()` assumes -> their actual, measured positions on the rig).
+///
+/// Scope note: this only corrects the emulator's own debug visualization (the wireframe in
+/// `Screen::new` and the reconstructed points via `calibrate_point`, both applied *after*
+/// `vdrm_alg::Codec::decode`). `vdrm_alg::Codec` itself is an external, unmodified dependency
+/// here with no way to pass it a per-screen homography, so the LED/angle data `Codec::encode`
+/// produces — what an actual rig would be driven with — is never calibrated. A real rig still
+/// needs calibration applied inside `Codec::encode`/`decode`; this only makes the emulator's own
+/// preview trustworthy on a misaligned rig, not the output that drives one.
+pub fn calibrate_screens(
+    panel_corners: [[(f32, f32); 4]; 3],
+    measured_corners: [[(f32, f32); 4]; 3],
+) -> [Homography; 3] {
+    std::array::from_fn(|i| Homography::from_correspondences(panel_corners[i], measured_corners[i]))
+}
+
+/// The perpendicular distance from `point` to the assumed (uncalibrated) panel segment
+/// `vdrm_alg::screens()[idx].xy_line`, used to work out which screen a reconstructed point came
+/// off of.
+fn distance_to_screen(point: (f32, f32), idx: usize) -> f32 {
+    let xy_line = vdrm_alg::screens()[idx].xy_line;
+    let (a, b) = xy_line.points();
+    let (ax, ay, bx, by) = (a.x(), a.y(), b.x(), b.y());
+    let (dx, dy) = (bx - ax, by - ay);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 > f32::EPSILON {
+        (((point.0 - ax) * dx + (point.1 - ay) * dy) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (cx, cy) = (ax + t * dx, ay + t * dy);
+    ((point.0 - cx).powi(2) + (point.1 - cy).powi(2)).sqrt()
+}
+
+/// Which of the three assumed screen panels a reconstructed point lies closest to.
+///
+/// This assumes a decoded point's raw `(x, y)` lands essentially on its originating screen's
+/// assumed line — true away from panel edges, but `vdrm_alg::Codec::decode` never labels which
+/// screen produced a point, so right where two panels' assumed lines run close together this is
+/// a guess, not a guarantee. See `calibrate_point_attributes_real_decoded_points_to_a_screen`
+/// for how that plays out against genuine `Codec::decode` output.
+fn nearest_screen(point: (f32, f32)) -> usize {
+    (0..3)
+        .min_by(|&a, &b| {
+            distance_to_screen(point, a)
+                .partial_cmp(&distance_to_screen(point, b))
+                .unwrap()
+        })
+        .unwrap()
+}
+
+/// `vdrm_alg::Codec::decode` reconstructs points assuming the ideal, uncalibrated panel
+/// positions from `vdrm_alg::screens()`. A point that decode placed at an assumed panel position
+/// is really sitting wherever that panel has actually been measured to be, so correcting it is a
+/// forward application of that panel's homography (same direction `Screen::new` uses for the
+/// wireframe), not an inverse.
+fn calibrate_point(point: (f32, f32, f32), calibration: &[Homography; 3]) -> (f32, f32, f32) {
+    let (x, y, z) = point;
+    let idx = nearest_screen((x, y));
+    let (cx, cy) = calibration[idx].apply((x, y));
+    (cx, cy, z)
+}
+
 struct Screen {
     points: [(f32, f32, f32); 4],
 }
 
 impl Screen {
-    fn new(idx: usize) -> Self {
+    fn new(idx: usize, calibration: Homography) -> Self {
         let xy_line = vdrm_alg::screens()[idx].xy_line;
         let (a, b) = xy_line.points();
+        let (ax, ay) = calibration.apply((a.x(), a.y()));
+        let (bx, by) = calibration.apply((b.x(), b.y()));
         let points = [
-            (a.x(), a.y(), -1.),
-            (a.x(), a.y(), 1.),
-            (b.x(), b.y(), 1.),
-            (b.x(), b.y(), -1.),
+            (ax, ay, -1.),
+            (ax, ay, 1.),
+            (bx, by, 1.),
+            (bx, by, -1.),
         ];
         Self { points }
     }
@@ -90,12 +471,35 @@ struct Ctx {
     all_emu_pixels: Vec<(f32, f32, f32)>,
     all_led_pixels: Vec<(f32, f32, f32)>,
     screens: [Screen; 3],
+    calibration: [Homography; 3],
+    // Kept around (rather than just consumed) so `set_calibration` can rebuild the scene with a
+    // new calibration without losing whatever model `load_model` last loaded.
+    pixel_surface: vdrm_alg::PixelSurface,
 }
 
 impl Ctx {
     fn new() -> Self {
+        Self::with_calibration([Homography::identity(); 3])
+    }
+
+    /// Same as `from_surface`, but builds the scene from the built-in test pyramid.
+    fn with_calibration(calibration: [Homography; 3]) -> Self {
+        Self::from_surface(gen_pyramid_surface(), calibration)
+    }
+
+    /// Builds the emulator's scene from an already-loaded `pixel_surface` (the pyramid, or a
+    /// model imported through a `SurfaceModelLoader`), applying a per-screen homography (see
+    /// `Homography`) to both the panel wireframe and the reconstructed point clouds, so a rig
+    /// whose panels are mounted slightly off from `vdrm_alg::screens()` still previews as a
+    /// correct surface in this emulator, rather than just drawing a corrected outline around a
+    /// wrong reconstruction.
+    ///
+    /// `codec.encode` below is NOT given the calibration: `vdrm_alg::Codec` is an external,
+    /// unmodified dependency with no hook for a per-screen homography, so the angle/LED data it
+    /// produces — what a real rig would actually be driven with — stays uncalibrated. Only this
+    /// emulator's own post-decode preview gets corrected; see `calibrate_screens`'s doc comment.
+    fn from_surface(pixel_surface: vdrm_alg::PixelSurface, calibration: [Homography; 3]) -> Self {
         let codec = vdrm_alg::Codec::new();
-        let pixel_surface = gen_pyramid_surface();
         let all_real_pixels = vdrm_alg::pixel_surface_to_float(&pixel_surface)
             .into_iter()
             .map(|(x, y, z)| (x, y, z - 2.))
@@ -116,6 +520,14 @@ impl Ctx {
                     );
                 };
                 let (emu_pixels, led_pixels) = codec.decode(angle, lines);
+                let emu_pixels: Vec<_> = emu_pixels
+                    .into_iter()
+                    .map(|p| calibrate_point(p, &calibration))
+                    .collect();
+                let led_pixels: Vec<_> = led_pixels
+                    .into_iter()
+                    .map(|p| calibrate_point(p, &calibration))
+                    .collect();
                 all_emu_pixels.extend(emu_pixels.clone());
                 all_led_pixels.extend(led_pixels.clone());
                 let angle_ctx = AngleCtx {
@@ -132,18 +544,257 @@ impl Ctx {
             all_real_pixels,
             all_emu_pixels,
             all_led_pixels,
-            screens: [0, 1, 2].map(|idx| Screen::new(idx)),
+            screens: [0, 1, 2].map(|idx| Screen::new(idx, calibration[idx])),
+            calibration,
+            pixel_surface,
+        }
+    }
+}
+
+/// Which built-in `SurfaceModelLoader` to parse uploaded model bytes with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModelFormat {
+    Vox,
+    Ply,
+}
+
+impl ModelFormat {
+    fn loader(self) -> &'static dyn SurfaceModelLoader {
+        match self {
+            ModelFormat::Vox => &VoxModel,
+            ModelFormat::Ply => &PlyModel,
+        }
+    }
+}
+
+/// Replaces the emulator's scene with a model parsed from `data` as `format`, keeping the
+/// screens' current calibration. This is the app's actual entry point for the loaders in
+/// `SurfaceModelLoader` — without it they're reachable from tests but never from the running
+/// emulator.
+pub fn load_model(data: &[u8], format: ModelFormat) -> Result<(), ModelLoadError> {
+    let pixel_surface = format.loader().load(data)?;
+    let mut ctx = CTX.lock().unwrap();
+    let calibration = ctx.calibration;
+    *ctx = Ctx::from_surface(pixel_surface, calibration);
+    Ok(())
+}
+
+/// Installs a newly solved per-screen calibration (see `calibrate_screens`) into the running
+/// emulator, keeping whatever model is currently loaded. Without this, `calibrate_screens` has
+/// no way to reach the live scene: it just returns `[Homography; 3]` that nothing ever applies.
+pub fn set_calibration(calibration: [Homography; 3]) {
+    let mut ctx = CTX.lock().unwrap();
+    let pixel_surface = ctx.pixel_surface.clone();
+    *ctx = Ctx::from_surface(pixel_surface, calibration);
+}
+
+const AXIS_LEN: f32 = 1.5;
+
+// Shared by the live canvas view and `render_revolution` so both paths sit behind the same
+// pitch/yaw handling instead of drifting apart.
+fn projection_matrix(pixel_range: (Range<i32>, Range<i32>), pitch: f64, yaw: f64) -> ProjectionMatrix {
+    let (x, y) = pixel_range;
+    let v = (x.end - x.start).min(y.end - y.start) * 4 / 5 / 2;
+    let before = (v, v, v);
+    let after = ((x.start + x.end) / 2, (y.start + y.end) / 2);
+
+    let mut mat = if before == (0, 0, 0) {
+        ProjectionMatrix::default()
+    } else {
+        let (x, y, z) = before;
+        ProjectionMatrix::shift(-x as f64, -y as f64, -z as f64) * ProjectionMatrix::default()
+    };
+    if yaw.abs() > 1e-20 {
+        mat = mat * ProjectionMatrix::rotate(0.0, 0.0, yaw);
+    }
+    if pitch.abs() > 1e-20 {
+        mat = mat * ProjectionMatrix::rotate(pitch, 0.0, 0.0);
+    }
+    mat = mat * ProjectionMatrix::scale(0.7);
+    if after != (0, 0) {
+        let (x, y) = after;
+        mat = mat * ProjectionMatrix::shift(x as f64, y as f64, 0.0);
+    }
+    mat
+}
+
+/// A pinhole camera: everything a single observer standing at `eye` and looking along `forward`
+/// would actually see of the emulator's point clouds, with points behind nearer ones culled via
+/// a z-buffer instead of drawn through like an x-ray.
+pub struct Pinhole {
+    eye: glam::Vec3,
+    forward: glam::Vec3,
+    up: glam::Vec3,
+    focal_length: f32,
+    width: u32,
+    height: u32,
+}
+
+impl Pinhole {
+    pub fn new(
+        eye: glam::Vec3,
+        forward: glam::Vec3,
+        up: glam::Vec3,
+        focal_length: f32,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            eye,
+            forward: forward.normalize(),
+            up: up.normalize(),
+            focal_length,
+            width,
+            height,
+        }
+    }
+
+    fn camera_basis(&self) -> (glam::Vec3, glam::Vec3, glam::Vec3) {
+        let z = self.forward;
+        let x = z.cross(self.up).normalize();
+        let y = x.cross(z);
+        (x, y, z)
+    }
+
+    /// Projects a world-space point into `(pixel, depth)`, or `None` if it falls behind the eye.
+    fn project(&self, point: (f32, f32, f32)) -> Option<((i32, i32), f32)> {
+        let (x_axis, y_axis, z_axis) = self.camera_basis();
+        let rel = glam::Vec3::new(point.0, point.1, point.2) - self.eye;
+        let depth = rel.dot(z_axis);
+        if depth <= 0.0 {
+            return None;
+        }
+        let cam_x = rel.dot(x_axis);
+        let cam_y = rel.dot(y_axis);
+        let px = self.focal_length * (cam_x / depth);
+        let py = self.focal_length * (cam_y / depth);
+        let col = (px + self.width as f32 / 2.0).round() as i32;
+        let row = (self.height as f32 / 2.0 - py).round() as i32;
+        if col < 0 || col >= self.width as i32 || row < 0 || row >= self.height as i32 {
+            return None;
+        }
+        Some(((col, row), depth))
+    }
+
+    /// Keeps only the nearest point per pixel, i.e. what the observer actually sees, paired with
+    /// the pixel it lands on.
+    pub fn visible_points(&self, points: &[(f32, f32, f32)]) -> Vec<((i32, i32), (f32, f32, f32))> {
+        let mut z_buffer: BTreeMap<(i32, i32), (f32, (f32, f32, f32))> = BTreeMap::new();
+        for &point in points {
+            let Some((pixel, depth)) = self.project(point) else {
+                continue;
+            };
+            z_buffer
+                .entry(pixel)
+                .and_modify(|(best_depth, best_point)| {
+                    if depth < *best_depth {
+                        *best_depth = depth;
+                        *best_point = point;
+                    }
+                })
+                .or_insert((depth, point));
         }
+        z_buffer
+            .into_iter()
+            .map(|(pixel, (_, point))| (pixel, point))
+            .collect()
     }
 }
 
-pub fn draw(canvas: HtmlCanvasElement, angle: Option<u32>, pitch: f64, yaw: f64) -> DrawResult<()> {
+/// Renders what a single `Pinhole` observer sees of the reconstruction: `all_emu_pixels`/
+/// `all_led_pixels` (or, for a specific `angle`, just that angle's slice) after occlusion.
+pub fn draw_observed(
+    canvas: HtmlCanvasElement,
+    pinhole: &Pinhole,
+    angle: Option<u32>,
+) -> DrawResult<()> {
     let area = CanvasBackend::with_canvas_object(canvas)
         .unwrap()
         .into_drawing_area();
     area.fill(&WHITE)?;
 
-    let axis_len = 1.5_f32;
+    let (emu, led) = {
+        let ctx = CTX.lock().unwrap();
+        match angle {
+            None => (ctx.all_emu_pixels.clone(), ctx.all_led_pixels.clone()),
+            Some(angle) => {
+                let angle_ctx = ctx.angle_ctx_map.get(&angle).unwrap();
+                (angle_ctx.emu_pixels.clone(), angle_ctx.led_pixels.clone())
+            }
+        }
+    };
+
+    let emu_points = pinhole.visible_points(&emu);
+    let led_points = pinhole.visible_points(&led);
+
+    area.draw_series(
+        emu_points
+            .into_iter()
+            .map(|(pixel, _)| Circle::new(pixel, 1, RED.mix(0.3).filled())),
+    )?;
+    area.draw_series(
+        led_points
+            .into_iter()
+            .map(|(pixel, _)| Circle::new(pixel, 1, RED.mix(0.8).filled())),
+    )?;
+
+    Ok(())
+}
+
+/// How the reconstructed point clouds (`emu`/`led` pixels) are colored when drawn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorMode {
+    /// Flat color per point cloud, as before.
+    Source,
+    /// Color each point by its normalized `z` through a Turbo-like colormap, so depth and any
+    /// reconstruction artifacts are easier to read than the flat 3-bit source colors.
+    Depth,
+}
+
+/// A compact piecewise-linear approximation of Google's Turbo colormap.
+fn turbo_color(t: f32) -> RGBColor {
+    const STOPS: [(f32, (u8, u8, u8)); 7] = [
+        (0.0, (48, 18, 59)),
+        (0.17, (70, 107, 227)),
+        (0.33, (44, 185, 210)),
+        (0.5, (78, 222, 115)),
+        (0.67, (216, 210, 48)),
+        (0.83, (233, 112, 38)),
+        (1.0, (122, 4, 3)),
+    ];
+    let t = t.clamp(0.0, 1.0);
+    for pair in STOPS.windows(2) {
+        let (t0, (r0, g0, b0)) = pair[0];
+        let (t1, (r1, g1, b1)) = pair[1];
+        if t > t1 {
+            continue;
+        }
+        let f = ((t - t0) / (t1 - t0)).clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f).round() as u8;
+        return RGBColor(lerp(r0, r1), lerp(g0, g1), lerp(b0, b1));
+    }
+    let (_, (r, g, b)) = STOPS[STOPS.len() - 1];
+    RGBColor(r, g, b)
+}
+
+/// Maps a point's `z`, normalized over `-axis_len..axis_len`, to a depth color.
+fn depth_color(z: f32, axis_len: f32) -> RGBColor {
+    turbo_color((z + axis_len) / (2.0 * axis_len))
+}
+
+pub fn draw(
+    canvas: HtmlCanvasElement,
+    angle: Option<u32>,
+    pitch: f64,
+    yaw: f64,
+    color_mode: ColorMode,
+) -> DrawResult<()> {
+    let area = CanvasBackend::with_canvas_object(canvas)
+        .unwrap()
+        .into_drawing_area();
+    area.fill(&WHITE)?;
+
+    let axis_len = AXIS_LEN;
     let x_axis = (-axis_len..axis_len).step(0.1);
     let y_axis = (-axis_len..axis_len).step(0.1);
 
@@ -152,31 +803,8 @@ pub fn draw(canvas: HtmlCanvasElement, angle: Option<u32>, pitch: f64, yaw: f64)
         y_axis.clone(),
         -axis_len..axis_len,
     )?;
-    chart.with_projection(| _pb| {
-        let (x, y) = area.get_pixel_range();
-        let v = (x.end - x.start).min(y.end - y.start) * 4 / 5 / 2;
-        let before = (v, v, v);
-        let after = ((x.start + x.end) / 2, (y.start + y.end) / 2);
-
-        let mut mat = if before == (0, 0, 0) {
-            ProjectionMatrix::default()
-        } else {
-            let (x, y, z) = before;
-            ProjectionMatrix::shift(-x as f64, -y as f64, -z as f64) * ProjectionMatrix::default()
-        };
-        if yaw.abs() > 1e-20 {
-            mat = mat * ProjectionMatrix::rotate(0.0, 0.0, yaw);
-        }
-        if pitch.abs() > 1e-20 {
-            mat = mat * ProjectionMatrix::rotate(pitch, 0.0, 0.0);
-        }
-        mat = mat * ProjectionMatrix::scale(0.7);
-        if after != (0, 0) {
-            let (x, y) = after;
-            mat = mat * ProjectionMatrix::shift(x as f64, y as f64, 0.0);
-        }
-        mat
-    });
+    let pixel_range = area.get_pixel_range();
+    chart.with_projection(move |_pb| projection_matrix(pixel_range.clone(), pitch, yaw));
 
     chart.configure_axes().draw()?;
 
@@ -196,7 +824,33 @@ pub fn draw(canvas: HtmlCanvasElement, angle: Option<u32>, pitch: f64, yaw: f64)
             }),
         )
         .unwrap();
-    let screen_polygons = CTX.screens.iter().map(|v| v.polygon());
+    let (screen_polygons, all_real_pixels, mirror_polygon, emu, led) = {
+        let ctx = CTX.lock().unwrap();
+        let screen_polygons: Vec<_> = ctx.screens.iter().map(|v| v.polygon()).collect();
+        let (mirror_polygon, emu, led) = match angle {
+            None => (
+                None,
+                ctx.all_emu_pixels.clone(),
+                ctx.all_led_pixels.clone(),
+            ),
+            Some(angle) => {
+                let angle_ctx = ctx.angle_ctx_map.get(&angle).unwrap();
+                (
+                    Some(angle_ctx.mirror.polygon()),
+                    angle_ctx.emu_pixels.clone(),
+                    angle_ctx.led_pixels.clone(),
+                )
+            }
+        };
+        (
+            screen_polygons,
+            ctx.all_real_pixels.clone(),
+            mirror_polygon,
+            emu,
+            led,
+        )
+    };
+
     chart
         .draw_series(screen_polygons)?
         .label("SCREEN")
@@ -204,40 +858,378 @@ pub fn draw(canvas: HtmlCanvasElement, angle: Option<u32>, pitch: f64, yaw: f64)
             Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], BLACK.mix(0.9).filled())
         });
     let real_surface_points: PointSeries<_, _, Circle<_, _>, _> =
-        PointSeries::new(CTX.all_real_pixels.clone(), 1_f64, &BLUE.mix(0.2));
+        PointSeries::new(all_real_pixels, 1_f64, &BLUE.mix(0.2));
     chart
         .draw_series(real_surface_points)?
         .label("REAL")
         .legend(|(x, y)| Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], BLUE.mix(0.5).filled()));
 
-    let (emu, led) = match angle {
-        None => {
-            (CTX.all_emu_pixels.clone(), CTX.all_led_pixels.clone())
-        }
-        Some(angle) => {
-            let angle_ctx = CTX.angle_ctx_map.get(&angle).unwrap();
+    if let Some(mirror_polygon) = mirror_polygon {
+        chart
+            .draw_series([mirror_polygon])?
+            .label("MIRROR")
+            .legend(|(x, y)| {
+                Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], BLACK.mix(0.5).filled())
+            });
+    }
+
+    match color_mode {
+        ColorMode::Source => {
+            let emu_surface_points: PointSeries<_, _, Circle<_, _>, _> =
+                PointSeries::new(emu, 1_f64, &RED.mix(0.3));
             chart
-                .draw_series([angle_ctx.mirror.polygon()])?
-                .label("MIRROR")
+                .draw_series(emu_surface_points)?
+                .label("EMULATOR")
                 .legend(|(x, y)| {
-                    Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], BLACK.mix(0.5).filled())
+                    Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], RED.mix(0.5).filled())
                 });
 
-            (angle_ctx.emu_pixels.clone(), angle_ctx.led_pixels.clone())
+            let led_surface_points: PointSeries<_, _, Circle<_, _>, _> =
+                PointSeries::new(led, 1_f64, &RED.mix(0.8));
+            chart.draw_series(led_surface_points)?;
         }
-    };
-
-    let emu_surface_points: PointSeries<_, _, Circle<_, _>, _> =
-        PointSeries::new(emu, 1_f64, &RED.mix(0.3));
-    chart
-        .draw_series(emu_surface_points)?
-        .label("EMULATOR")
-        .legend(|(x, y)| Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], RED.mix(0.5).filled()));
+        ColorMode::Depth => {
+            // A representative mid-range swatch, since points are no longer one flat RED: the
+            // legend should look like what the colormap actually draws, not the old Source-mode
+            // color it replaced.
+            let legend_color = turbo_color(0.5);
+            chart
+                .draw_series(emu.iter().map(|&(x, y, z)| {
+                    Circle::new((x, y, z), 1, depth_color(z, axis_len).mix(0.6).filled())
+                }))?
+                .label("EMULATOR")
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], legend_color.mix(0.6).filled())
+                });
 
-    let led_surface_points: PointSeries<_, _, Circle<_, _>, _> =
-        PointSeries::new(led, 1_f64, &RED.mix(0.8));
-    chart.draw_series(led_surface_points)?;
+            chart
+                .draw_series(
+                    led.iter()
+                        .map(|&(x, y, z)| Circle::new((x, y, z), 1, depth_color(z, axis_len).filled())),
+                )?
+                .label("LED")
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x + 5, y - 5), (x + 15, y + 5)], legend_color.filled())
+                });
+        }
+    }
 
     chart.configure_series_labels().border_style(BLACK).draw()?;
+
+    // `Cartesian3d` has no `ReverseCoordTranslate` impl, and couldn't: a 3D perspective
+    // projection collapses a pixel to a ray, not a point, so there's no well-defined inverse.
+    // Keep the forward coordinate spec instead (it implements the base `CoordTranslate` in both
+    // directions plotters actually supports), so `pick` can project known points forward and
+    // match against the click rather than trying to invert it.
+    let coord_spec = chart.as_coord_spec().clone();
+    *LAST_PROJECTION.lock().unwrap() = Some(Box::new(move |p| coord_spec.translate(&p)));
+
+    Ok(())
+}
+
+fn pixel_dist2(a: (i32, i32), b: (i32, i32)) -> i64 {
+    let (dx, dy) = ((a.0 - b.0) as i64, (a.1 - b.1) as i64);
+    dx * dx + dy * dy
+}
+
+/// Finds which known reconstructed point a clicked canvas pixel landed on, using the projection
+/// from the most recent `draw()` call, then reports it along with the mirror `angle` that
+/// produced it. Projects every candidate point forward through that projection and keeps the one
+/// landing closest to `(canvas_x, canvas_y)`, rather than reverse-projecting the click (which a
+/// 3D perspective projection doesn't support). Returns `None` if nothing has been drawn yet or
+/// there are no points to match against.
+pub fn pick(canvas_x: i32, canvas_y: i32) -> Option<(u32, (f32, f32, f32))> {
+    let guard = LAST_PROJECTION.lock().unwrap();
+    let project = guard.as_ref()?;
+    let click = (canvas_x, canvas_y);
+
+    let ctx = CTX.lock().unwrap();
+    ctx.angle_ctx_map
+        .iter()
+        .flat_map(|(&angle, ctx)| {
+            ctx.led_pixels
+                .iter()
+                .chain(ctx.emu_pixels.iter())
+                .map(move |&point| (angle, point))
+        })
+        .min_by_key(|(_, point)| pixel_dist2(project(*point), click))
+}
+
+/// Renders every mirror angle as one frame of an animated GIF, accumulating the emulator's
+/// persistence-of-vision reconstruction as the mirror sweeps through `0..vdrm_alg::TOTAL_ANGLES`.
+pub fn render_revolution(path: &str, size: (u32, u32), pitch: f64, yaw: f64) -> DrawResult<()> {
+    let frame_delay_ms = 1000 / vdrm_alg::TOTAL_ANGLES.max(1) as u32;
+    let root = BitMapBackend::gif(path, size, frame_delay_ms)?.into_drawing_area();
+
+    let axis_len = AXIS_LEN;
+    let x_axis = (-axis_len..axis_len).step(0.1);
+    let y_axis = (-axis_len..axis_len).step(0.1);
+
+    let mut emu_acc: Vec<(f32, f32, f32)> = vec![];
+    let mut led_acc: Vec<(f32, f32, f32)> = vec![];
+
+    for angle in 0..vdrm_alg::TOTAL_ANGLES as u32 {
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root).build_cartesian_3d(
+            x_axis.clone(),
+            y_axis.clone(),
+            -axis_len..axis_len,
+        )?;
+        let pixel_range = root.get_pixel_range();
+        chart.with_projection(move |_pb| projection_matrix(pixel_range.clone(), pitch, yaw));
+        chart.configure_axes().draw()?;
+
+        let (screen_polygons, mirror_polygon, emu_pixels, led_pixels) = {
+            let ctx = CTX.lock().unwrap();
+            let screen_polygons: Vec<_> = ctx.screens.iter().map(|v| v.polygon()).collect();
+            let angle_ctx = ctx.angle_ctx_map.get(&angle).unwrap();
+            (
+                screen_polygons,
+                angle_ctx.mirror.polygon(),
+                angle_ctx.emu_pixels.clone(),
+                angle_ctx.led_pixels.clone(),
+            )
+        };
+        chart.draw_series(screen_polygons)?;
+        chart.draw_series([mirror_polygon])?;
+
+        emu_acc.extend(emu_pixels);
+        led_acc.extend(led_pixels);
+
+        let emu_surface_points: PointSeries<_, _, Circle<_, _>, _> =
+            PointSeries::new(emu_acc.clone(), 1_f64, &RED.mix(0.3));
+        chart.draw_series(emu_surface_points)?;
+
+        let led_surface_points: PointSeries<_, _, Circle<_, _>, _> =
+            PointSeries::new(led_acc.clone(), 1_f64, &RED.mix(0.8));
+        chart.draw_series(led_surface_points)?;
+
+        root.present()?;
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homography_round_trip() {
+        let src = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let dst = [(0.1, -0.2), (1.3, 0.05), (0.9, 1.2), (-0.1, 0.95)];
+        let h = Homography::from_correspondences(src, dst);
+        let inverse = h.inverse();
+        for p in src {
+            let back = inverse.apply(h.apply(p));
+            assert!((back.0 - p.0).abs() < 1e-3, "{back:?} vs {p:?}");
+            assert!((back.1 - p.1).abs() < 1e-3, "{back:?} vs {p:?}");
+        }
+    }
+
+    #[test]
+    fn homography_identity_is_noop() {
+        let h = Homography::identity();
+        let p = (0.3, -1.7);
+        assert_eq!(h.apply(p), p);
+    }
+
+    fn screen_midpoint(idx: usize) -> (f32, f32) {
+        let xy_line = vdrm_alg::screens()[idx].xy_line;
+        let (a, b) = xy_line.points();
+        ((a.x() + b.x()) / 2.0, (a.y() + b.y()) / 2.0)
+    }
+
+    #[test]
+    fn nearest_screen_matches_the_closest_assumed_panel() {
+        for idx in 0..3 {
+            assert_eq!(nearest_screen(screen_midpoint(idx)), idx);
+        }
+    }
+
+    #[test]
+    fn calibrate_point_applies_the_matching_screens_homography() {
+        let src = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        // A distinct, non-identity calibration per screen, so misattributing a point to the
+        // wrong screen's homography would produce a visibly wrong result.
+        let offsets = [(5.0, 0.0), (0.0, 5.0), (-5.0, -5.0)];
+        let mut calibration = [Homography::identity(); 3];
+        for (idx, (ox, oy)) in offsets.into_iter().enumerate() {
+            let dst = src.map(|(x, y)| (x + ox, y + oy));
+            calibration[idx] = Homography::from_correspondences(src, dst);
+        }
+
+        for idx in 0..3 {
+            let (mx, my) = screen_midpoint(idx);
+            let corrected = calibrate_point((mx, my, 0.0), &calibration);
+            let expected = calibration[idx].apply((mx, my));
+            assert!((corrected.0 - expected.0).abs() < 1e-3, "{corrected:?} vs {expected:?}");
+            assert!((corrected.1 - expected.1).abs() < 1e-3, "{corrected:?} vs {expected:?}");
+            assert_eq!(corrected.2, 0.0);
+        }
+    }
+
+    #[test]
+    fn calibrate_point_attributes_real_decoded_points_to_a_screen() {
+        // Unlike `calibrate_point_applies_the_matching_screens_homography`'s synthetic exact
+        // midpoints, exercise `nearest_screen`'s attribution against genuine
+        // `vdrm_alg::Codec::encode`/`decode` output for the built-in pyramid, including any
+        // points decoded near where two screens' assumed lines run close together — the
+        // boundary case `nearest_screen`'s doc comment says is a guess, not a guarantee. This
+        // doesn't prove any point is attributed to the "right" screen (decode doesn't label
+        // one), only that every decoded point resolves to one of the three screens without
+        // panicking. `closest_gap` records how near the two closest screens' distances come,
+        // but is only checked to be finite, not to reach a tie.
+        let codec = vdrm_alg::Codec::new();
+        let pixel_surface = gen_pyramid_surface();
+        let angle_map = codec.encode(&pixel_surface, 0);
+
+        let mut checked_any = false;
+        let mut closest_gap = f32::MAX;
+        for (angle, lines) in &angle_map {
+            let (emu_pixels, led_pixels) = codec.decode(*angle, lines);
+            for (x, y, _z) in emu_pixels.into_iter().chain(led_pixels) {
+                checked_any = true;
+                let mut distances: Vec<f32> = (0..3).map(|idx| distance_to_screen((x, y), idx)).collect();
+                distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                closest_gap = closest_gap.min(distances[1] - distances[0]);
+                // Must resolve to some screen without panicking, even right at a near-tie.
+                assert!(nearest_screen((x, y)) < 3);
+            }
+        }
+
+        assert!(checked_any, "pyramid model produced no decoded points to attribute");
+        assert!(closest_gap.is_finite());
+    }
+
+    /// Builds one vox chunk's bytes: 4-byte id, content, and (pre-built) children.
+    fn vox_chunk(id: &[u8; 4], content: &[u8], children: &[u8]) -> Vec<u8> {
+        let mut out = vec![];
+        out.extend_from_slice(id);
+        out.extend((content.len() as u32).to_le_bytes());
+        out.extend((children.len() as u32).to_le_bytes());
+        out.extend_from_slice(content);
+        out.extend_from_slice(children);
+        out
+    }
+
+    #[test]
+    fn vox_model_descends_into_main_chunk_children() {
+        let size_content: Vec<u8> = [4_u32, 4, 2].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let size_chunk = vox_chunk(b"SIZE", &size_content, &[]);
+
+        let mut xyzi_content = vec![];
+        xyzi_content.extend(2_u32.to_le_bytes());
+        xyzi_content.extend([1, 1, 0, 1]); // x, y, z, color_index
+        xyzi_content.extend([2, 2, 1, 2]);
+        let xyzi_chunk = vox_chunk(b"XYZI", &xyzi_content, &[]);
+
+        let mut rgba_content = vec![];
+        rgba_content.extend([255, 0, 0, 255]); // palette[0], selected by color_index 1
+        rgba_content.extend([0, 255, 0, 255]); // palette[1], selected by color_index 2
+        let rgba_chunk = vox_chunk(b"RGBA", &rgba_content, &[]);
+
+        let children = [size_chunk, xyzi_chunk, rgba_chunk].concat();
+        let main_chunk = vox_chunk(b"MAIN", &[], &children);
+
+        let mut data = vec![];
+        data.extend(b"VOX ");
+        data.extend(150_u32.to_le_bytes());
+        data.extend(main_chunk);
+
+        let surface = VoxModel.load(&data).unwrap();
+        assert_eq!(surface, vec![(1, 1, (0, 0b100)), (2, 2, (63, 0b010))]);
+    }
+
+    #[test]
+    fn vox_model_rejects_bad_magic() {
+        let err = VoxModel.load(b"nope").unwrap_err();
+        assert!(matches!(err, ModelLoadError::BadMagic));
+    }
+
+    fn straight_on_pinhole(width: u32, height: u32) -> Pinhole {
+        Pinhole::new(
+            glam::Vec3::new(0.0, 0.0, -5.0),
+            glam::Vec3::new(0.0, 0.0, 1.0),
+            glam::Vec3::new(0.0, 1.0, 0.0),
+            100.0,
+            width,
+            height,
+        )
+    }
+
+    #[test]
+    fn pinhole_project_rejects_non_positive_depth() {
+        let pinhole = straight_on_pinhole(64, 64);
+        // Behind the eye and exactly at the eye both have depth <= 0 and must be rejected.
+        assert!(pinhole.project((0.0, 0.0, -10.0)).is_none());
+        assert!(pinhole.project((0.0, 0.0, -5.0)).is_none());
+        assert!(pinhole.project((0.0, 0.0, 5.0)).is_some());
+    }
+
+    #[test]
+    fn pinhole_visible_points_keeps_the_nearer_point_per_pixel() {
+        let pinhole = straight_on_pinhole(64, 64);
+        // Both points sit on the same ray out from the eye, so they land on the same pixel; the
+        // z-buffer must keep the nearer one and drop the farther one rather than drawing both.
+        let near = (0.0, 0.0, 1.0);
+        let far = (0.0, 0.0, 10.0);
+        let visible = pinhole.visible_points(&[far, near]);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].1, near);
+    }
+
+    #[test]
+    fn pinhole_visible_points_keeps_distinct_pixels_separately() {
+        let pinhole = straight_on_pinhole(64, 64);
+        let left = (-0.1, 0.0, 1.0);
+        let right = (0.1, 0.0, 1.0);
+        let visible = pinhole.visible_points(&[left, right]);
+        assert_eq!(visible.len(), 2);
+    }
+
+    #[test]
+    fn pick_finds_the_closer_of_two_candidate_points() {
+        // An identity-ish projection (pixel == rounded (x, y)) so the test can reason about
+        // pixel distances directly instead of through a real chart projection.
+        *LAST_PROJECTION.lock().unwrap() = Some(Box::new(|(x, y, _z): (f32, f32, f32)| {
+            (x.round() as i32, y.round() as i32)
+        }));
+
+        {
+            let mut ctx = CTX.lock().unwrap();
+            ctx.angle_ctx_map.clear();
+            ctx.angle_ctx_map.insert(
+                7,
+                AngleCtx {
+                    mirror: Mirror::new(1.0, 0),
+                    led_pixels: vec![(10.0, 10.0, 0.0)],
+                    emu_pixels: vec![(0.0, 0.0, 0.0)],
+                },
+            );
+        }
+
+        let (angle, point) = pick(1, 1).expect("a candidate point should be found");
+        assert_eq!(angle, 7);
+        assert_eq!(point, (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn pick_returns_none_before_anything_has_been_drawn() {
+        *LAST_PROJECTION.lock().unwrap() = None;
+        assert!(pick(0, 0).is_none());
+    }
+
+    #[test]
+    fn ply_model_quantizes_corners_to_grid_bounds() {
+        let ply = "ply\nformat ascii 1.0\n\
+                   element vertex 2\n\
+                   property float x\nproperty float y\nproperty float z\n\
+                   property uchar red\nproperty uchar green\nproperty uchar blue\n\
+                   end_header\n\
+                   0 0 0 255 0 0\n\
+                   10 10 10 0 255 0\n";
+        let surface = PlyModel.load(ply.as_bytes()).unwrap();
+        assert_eq!(surface, vec![(0, 0, (0, 0b100)), (63, 63, (63, 0b010))]);
+    }
+}